@@ -0,0 +1,18 @@
+use parser::lexer::{BytePos, Span};
+
+/// An error produced while lexing malformed input.
+#[derive(Debug, PartialEq, Eq)]
+pub enum SyntaxError {
+    /// A numeric literal could not be parsed into its target type, most
+    /// commonly because it overflows. Carries the span of the offending
+    /// literal.
+    InvalidNumber { span: Span },
+
+    /// A quoted string or identifier was not closed before the end of input.
+    /// Carries the span starting at the opening quote.
+    UnterminatedLiteral { span: Span },
+
+    /// Input contained a character that cannot start any token. Carries the
+    /// offending character and its byte position.
+    UnexpectedChar { ch: char, pos: BytePos },
+}
@@ -1,29 +1,80 @@
+use std::borrow::Cow;
 use std::str::FromStr;
 
+use unicode_xid::UnicodeXID;
+
 use parser::token::{Token, Keyword, Op};
 use parser::errors::SyntaxError;
 
 pub type BytePos = usize;
 
-/// Encapsulates a token and the byte positions it spans.
-#[derive(Debug, PartialEq, Eq)]
-pub struct LexedToken {
+/// Encapsulates a token, the byte positions it spans and its line/column range.
+///
+/// The `'a` lifetime is that of the input being lexed: `Comment` tokens, and
+/// `Ident` tokens that needed no case folding or unescaping, point directly
+/// into it.
+#[derive(Debug, PartialEq)]
+pub struct LexedToken<'a> {
     /// The token that was found.
-    pub token: Token,
-    /// Where the token was found.
+    pub token: Token<'a>,
+    /// The byte range the token was found at.
     pub span: Span,
+    /// The line/column range the token was found at, for diagnostics.
+    pub lines: LineColSpan,
 }
 
-impl LexedToken {
-    fn new(token: Token, start: BytePos, end: BytePos) -> LexedToken {
+impl<'a> LexedToken<'a> {
+    fn new(token: Token<'a>,
+           start: BytePos,
+           end: BytePos,
+           start_lc: LineCol,
+           end_lc: LineCol)
+           -> LexedToken<'a> {
         LexedToken {
             token: token,
             span: Span::new(start, end),
+            lines: LineColSpan::new(start_lc, end_lc),
         }
     }
 
-    fn new_at(token: Token, pos: BytePos) -> LexedToken {
-        LexedToken::new(token, pos, pos)
+    fn new_at(token: Token<'a>, pos: BytePos, lc: LineCol) -> LexedToken<'a> {
+        LexedToken::new(token, pos, pos, lc, lc)
+    }
+}
+
+/// A one-based line and column position within the input.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct LineCol {
+    /// One-based line number.
+    pub line: usize,
+    /// One-based column, measured in bytes from the start of the line.
+    pub column: usize,
+}
+
+impl LineCol {
+    pub fn new(line: usize, column: usize) -> LineCol {
+        LineCol {
+            line: line,
+            column: column,
+        }
+    }
+}
+
+/// Represents a line/column range of a text segment, mirroring `Span`.
+#[derive(Debug, PartialEq, Eq)]
+pub struct LineColSpan {
+    /// Line/column where the text segment starts.
+    pub start: LineCol,
+    /// Line/column where the text segment ends.
+    pub end: LineCol,
+}
+
+impl LineColSpan {
+    pub fn new(start: LineCol, end: LineCol) -> LineColSpan {
+        LineColSpan {
+            start: start,
+            end: end,
+        }
     }
 }
 
@@ -59,6 +110,14 @@ pub struct StringReader<'a> {
 
     /// The current char.
     pub curr_char: Option<char>,
+
+    /// The line/column of the previously read char.
+    pub prev_line: usize,
+    pub prev_column: usize,
+
+    /// The line/column of the current char.
+    pub line: usize,
+    pub column: usize,
 }
 
 impl<'a> StringReader<'a> {
@@ -68,9 +127,23 @@ impl<'a> StringReader<'a> {
             prev_pos: 0,
             curr_pos: 0,
             curr_char: input.chars().next(),
+            prev_line: 1,
+            prev_column: 1,
+            line: 1,
+            column: 1,
         }
     }
 
+    /// Returns the line/column of the current char.
+    pub fn line_col(&self) -> LineCol {
+        LineCol::new(self.line, self.column)
+    }
+
+    /// Returns the line/column of the previously read char.
+    pub fn prev_line_col(&self) -> LineCol {
+        LineCol::new(self.prev_line, self.prev_column)
+    }
+
     /// Returns true if no more input to read, false otherwise.
     pub fn is_eof(&self) -> bool {
         self.curr_char.is_none()
@@ -84,12 +157,21 @@ impl<'a> StringReader<'a> {
         }
     }
 
-    /// Advances `prev_pos` and `curr_pos`
+    /// Advances `prev_pos` and `curr_pos`, tracking line and column.
     pub fn advance(&mut self) {
         self.prev_pos = self.curr_pos;
+        self.prev_line = self.line;
+        self.prev_column = self.column;
 
         if let Some(c) = self.curr_char {
             self.curr_pos += c.len_utf8();
+
+            if c == '\n' {
+                self.line += 1;
+                self.column = 1;
+            } else {
+                self.column += c.len_utf8();
+            }
         }
 
         self.curr_char = self.char_at(self.curr_pos);
@@ -103,9 +185,15 @@ impl<'a> StringReader<'a> {
     }
 
     /// Advances the string reader's position by the given number of bytes.
+    ///
+    /// Used for multi-byte operators, which never contain a newline, so the
+    /// column simply advances by `n_bytes`.
     pub fn advance_bytes(&mut self, n_bytes: usize) {
         self.prev_pos = self.curr_pos;
+        self.prev_line = self.line;
+        self.prev_column = self.column;
         self.curr_pos += n_bytes;
+        self.column += n_bytes;
         self.curr_char = self.char_at(self.curr_pos);
     }
 
@@ -131,37 +219,30 @@ impl<'a> StringReader<'a> {
         next_char.is_some() && next_char.unwrap() == c
     }
 
-    /// Reads the input into a `String` until a new line is found,
-    /// advancing the current position. Returns the read input.
-    pub fn read_line(&mut self) -> String {
-        let mut s = String::new();
+    /// Slices the input from the current position until a new line is found,
+    /// advancing the current position. Returns the borrowed line, excluding the
+    /// trailing newline.
+    pub fn slice_line(&mut self) -> &'a str {
+        let start = self.curr_pos;
 
         while !self.is_eof() && !self.is_eol() {
-            s.push(self.curr_char.unwrap());
             self.advance();
         }
 
+        let line = &self.input[start..self.curr_pos];
+
         // Move to start of next line.
         self.advance();
 
-        s
+        line
     }
 
-    /// Reads the input into a `String` while the given condition is met,
-    /// advancing the current position. Returns the read input.
-    pub fn read_while<F: Fn(char) -> bool>(&mut self, test: F) -> String {
-        let mut s = String::new();
-
-        while let Some(c) = self.curr_char {
-            if test(c) {
-                s.push(c);
-                self.advance();
-            } else {
-                break;
-            }
-        }
-
-        s
+    /// Slices the input from the current position while the given condition is
+    /// met, advancing the current position. Returns the borrowed slice.
+    pub fn slice_while<F: Fn(char) -> bool>(&mut self, test: F) -> &'a str {
+        let start = self.curr_pos;
+        self.advance_while(test);
+        &self.input[start..self.curr_pos]
     }
 
     /// Returns `Some(c)` containing the char at the specified byte position if found,
@@ -177,15 +258,20 @@ impl<'a> StringReader<'a> {
 
 pub struct Lexer<'a> {
     reader: StringReader<'a>,
+    /// Set once `Eof` (or an error) has been yielded, so iteration stops.
+    done: bool,
 }
 
 impl<'a> Lexer<'a> {
     pub fn new(input: &'a str) -> Lexer<'a> {
-        Lexer { reader: StringReader::new(input) }
+        Lexer {
+            reader: StringReader::new(input),
+            done: false,
+        }
     }
 
     /// Consumes input until a token is found, and returns that token.
-    pub fn next_token(&mut self) -> Result<LexedToken, SyntaxError> {
+    pub fn next_token(&mut self) -> Result<LexedToken<'a>, SyntaxError> {
         if let Some(t) = self.next_token_opt() {
             Ok(t)
         } else {
@@ -194,61 +280,77 @@ impl<'a> Lexer<'a> {
         }
     }
 
-    fn next_token_opt(&mut self) -> Option<LexedToken> {
+    fn next_token_opt(&mut self) -> Option<LexedToken<'a>> {
         self.scan_eof()
             .or_else(|| self.scan_whitespace())
             .or_else(|| self.scan_comment())
             .or_else(|| self.scan_operator())
     }
 
-    fn next_token_res(&mut self) -> Result<LexedToken, SyntaxError> {
+    fn next_token_res(&mut self) -> Result<LexedToken<'a>, SyntaxError> {
         match self.reader.curr_char {
             Some(c) if is_ident_start(c) => self.scan_keyword_or_unquoted_identifier(),
-            _ => unimplemented!(),
+            Some(c) if is_number_start(c, self.reader.peek_next()) => self.scan_number(),
+            Some('\'') => self.scan_string(),
+            Some('"') => self.scan_quoted_identifier(),
+            Some(c) => Err(SyntaxError::UnexpectedChar { ch: c, pos: self.reader.curr_pos }),
+            None => unreachable!("next_token guarantees a current char here"),
         }
     }
 
-    fn scan_whitespace(&mut self) -> Option<LexedToken> {
+    fn scan_whitespace(&mut self) -> Option<LexedToken<'a>> {
         let c = self.reader.curr_char.unwrap_or('\0');
 
         if c.is_whitespace() {
             let start = self.reader.curr_pos;
+            let start_lc = self.reader.line_col();
             self.consume_whitespace();
-            Some(LexedToken::new(Token::Whitespace, start, self.reader.prev_pos))
+            Some(LexedToken::new(Token::Whitespace,
+                                 start,
+                                 self.reader.prev_pos,
+                                 start_lc,
+                                 self.reader.prev_line_col()))
         } else {
             None
         }
     }
 
-    fn scan_comment(&mut self) -> Option<LexedToken> {
+    fn scan_comment(&mut self) -> Option<LexedToken<'a>> {
         if self.reader.curr_is('-') && self.reader.next_is('-') {
             let start = self.reader.curr_pos;
+            let start_lc = self.reader.line_col();
 
             // Move past the `--` characters.
             self.reader.advance();
             self.reader.advance();
 
-            let comment = self.reader.read_line();
-            Some(LexedToken::new(Token::Comment(comment), start, self.reader.prev_pos))
+            let comment = self.reader.slice_line();
+            Some(LexedToken::new(Token::Comment(comment),
+                                 start,
+                                 self.reader.prev_pos,
+                                 start_lc,
+                                 self.reader.prev_line_col()))
         } else {
             None
         }
     }
 
-    fn scan_eof(&mut self) -> Option<LexedToken> {
+    fn scan_eof(&mut self) -> Option<LexedToken<'a>> {
         if self.reader.is_eof() {
-            Some(LexedToken::new_at(Token::Eof, self.reader.prev_pos))
+            Some(LexedToken::new_at(Token::Eof,
+                                    self.reader.prev_pos,
+                                    self.reader.prev_line_col()))
         } else {
             None
         }
     }
 
-    fn scan_operator(&mut self) -> Option<LexedToken> {
+    fn scan_operator(&mut self) -> Option<LexedToken<'a>> {
         self.scan_multi_byte_operator()
             .or_else(|| self.scan_single_byte_operator())
     }
 
-    fn scan_single_byte_operator(&mut self) -> Option<LexedToken> {
+    fn scan_single_byte_operator(&mut self) -> Option<LexedToken<'a>> {
         let curr = self.reader.curr_char.unwrap();
 
         if !is_single_byte_op_char(curr) {
@@ -258,14 +360,15 @@ impl<'a> Lexer<'a> {
         match Op::from_str(curr.to_string().as_str()) {
             Ok(op) => {
                 let pos = self.reader.curr_pos;
+                let lc = self.reader.line_col();
                 self.reader.advance();
-                Some(LexedToken::new_at(Token::Op(op), pos))
+                Some(LexedToken::new_at(Token::Op(op), pos, lc))
             }
             _ => None,
         }
     }
 
-    fn scan_multi_byte_operator(&mut self) -> Option<LexedToken> {
+    fn scan_multi_byte_operator(&mut self) -> Option<LexedToken<'a>> {
         let curr = self.reader.curr_char.unwrap();
         if !is_multi_byte_op_start(curr) {
             return None;
@@ -283,29 +386,170 @@ impl<'a> Lexer<'a> {
                 let start = self.reader.curr_pos;
                 let end = start + s.len() - 1;
 
+                let start_lc = self.reader.line_col();
+                let end_lc = LineCol::new(start_lc.line, start_lc.column + s.len() - 1);
+
                 self.reader.advance_bytes(s.len());
 
-                Some(LexedToken::new(Token::Op(op), start, end))
+                Some(LexedToken::new(Token::Op(op), start, end, start_lc, end_lc))
             }
             _ => None,
         }
     }
 
-    fn scan_keyword_or_unquoted_identifier(&mut self) -> Result<LexedToken, SyntaxError> {
+    fn scan_keyword_or_unquoted_identifier(&mut self) -> Result<LexedToken<'a>, SyntaxError> {
         assert!(is_ident_start(self.reader.curr_char.unwrap()));
 
         let start = self.reader.curr_pos;
-        let ident = self.reader
-            .read_while(is_ident_cont)
-            .to_lowercase(); // Keywords and unquoted identifiers are case insensitive.
+        let start_lc = self.reader.line_col();
+        let raw = self.reader.slice_while(is_ident_cont);
 
-        let tok = if let Ok(keyword) = Keyword::from_str(&ident) {
+        // Keywords and unquoted identifiers are case insensitive. Keep the
+        // borrowed slice whenever it is already lower case and only allocate to
+        // fold mixed-case input.
+        let tok = if let Ok(keyword) = Keyword::from_str(raw) {
             Token::Keyword(keyword)
         } else {
-            Token::Ident(ident)
+            Token::Ident(fold_ident(raw))
+        };
+
+        Ok(LexedToken::new(tok, start, self.reader.prev_pos, start_lc, self.reader.prev_line_col()))
+    }
+
+    fn scan_number(&mut self) -> Result<LexedToken<'a>, SyntaxError> {
+        let start = self.reader.curr_pos;
+        let start_lc = self.reader.line_col();
+        let mut is_float = false;
+
+        // Integer part.
+        self.reader.advance_while(|c| c.is_ascii_digit());
+
+        // Fractional part: a `.` is only part of the number when it is followed
+        // by a digit, so `t1.` style member access keeps the trailing dot.
+        if self.reader.curr_is('.') &&
+           self.reader.peek_next().map_or(false, |c| c.is_ascii_digit()) {
+            is_float = true;
+            self.reader.advance();
+            self.reader.advance_while(|c| c.is_ascii_digit());
+        }
+
+        // Exponent part: `e`/`E`, an optional sign, then at least one digit.
+        if let Some(c) = self.reader.curr_char {
+            if (c == 'e' || c == 'E') && self.has_exponent() {
+                is_float = true;
+                self.reader.advance();
+                if self.reader.curr_is('+') || self.reader.curr_is('-') {
+                    self.reader.advance();
+                }
+                self.reader.advance_while(|c| c.is_ascii_digit());
+            }
+        }
+
+        let raw = &self.reader.input[start..self.reader.curr_pos];
+        let end = self.reader.prev_pos;
+
+        let token = if is_float {
+            match f64::from_str(raw) {
+                Ok(f) => Token::Float(f),
+                Err(_) => return Err(SyntaxError::InvalidNumber { span: Span::new(start, end) }),
+            }
+        } else {
+            match i64::from_str(raw) {
+                Ok(n) => Token::Int(n),
+                Err(_) => return Err(SyntaxError::InvalidNumber { span: Span::new(start, end) }),
+            }
         };
 
-        Ok(LexedToken::new(tok, start, self.reader.prev_pos))
+        Ok(LexedToken::new(token, start, end, start_lc, self.reader.prev_line_col()))
+    }
+
+    fn scan_string(&mut self) -> Result<LexedToken<'a>, SyntaxError> {
+        let start = self.reader.curr_pos;
+        let start_lc = self.reader.line_col();
+        self.reader.advance(); // opening quote
+
+        match self.read_quoted('\'') {
+            Some(value) => {
+                Ok(LexedToken::new(Token::String(value.into_owned()),
+                                   start,
+                                   self.reader.prev_pos,
+                                   start_lc,
+                                   self.reader.prev_line_col()))
+            }
+            None => Err(SyntaxError::UnterminatedLiteral {
+                span: Span::new(start, self.reader.prev_pos),
+            }),
+        }
+    }
+
+    fn scan_quoted_identifier(&mut self) -> Result<LexedToken<'a>, SyntaxError> {
+        let start = self.reader.curr_pos;
+        let start_lc = self.reader.line_col();
+        self.reader.advance(); // opening quote
+
+        // Delimited identifiers keep their case exactly, so the slice is used
+        // verbatim rather than being folded like an unquoted identifier.
+        match self.read_quoted('"') {
+            Some(value) => Ok(LexedToken::new(Token::Ident(value),
+                                              start,
+                                              self.reader.prev_pos,
+                                              start_lc,
+                                              self.reader.prev_line_col())),
+            None => Err(SyntaxError::UnterminatedLiteral {
+                span: Span::new(start, self.reader.prev_pos),
+            }),
+        }
+    }
+
+    /// Reads a quoted literal body up to the matching closing `quote`, folding
+    /// a doubled quote (`''` or `""`) into a single literal quote character.
+    /// Returns the body borrowed when it contains no escapes, owned otherwise,
+    /// or `None` if the input ends before the closing quote. Assumes the
+    /// opening quote has already been consumed.
+    fn read_quoted(&mut self, quote: char) -> Option<Cow<'a, str>> {
+        let mut seg_start = self.reader.curr_pos;
+        let mut buf: Option<String> = None;
+
+        loop {
+            match self.reader.curr_char {
+                None => return None,
+                Some(c) if c == quote => {
+                    let seg = &self.reader.input[seg_start..self.reader.curr_pos];
+
+                    if self.reader.next_is(quote) {
+                        let b = buf.get_or_insert_with(String::new);
+                        b.push_str(seg);
+                        b.push(quote);
+                        self.reader.advance(); // first quote
+                        self.reader.advance(); // second quote
+                        seg_start = self.reader.curr_pos;
+                    } else {
+                        self.reader.advance(); // closing quote
+                        return Some(match buf {
+                            Some(mut b) => {
+                                b.push_str(seg);
+                                Cow::Owned(b)
+                            }
+                            None => Cow::Borrowed(seg),
+                        });
+                    }
+                }
+                Some(_) => self.reader.advance(),
+            }
+        }
+    }
+
+    /// Returns `true` when the `e`/`E` under the cursor begins a well-formed
+    /// exponent (an optional sign followed by at least one digit).
+    fn has_exponent(&self) -> bool {
+        let bytes = self.reader.input.as_bytes();
+        let mut i = self.reader.curr_pos + 1;
+
+        if i < bytes.len() && (bytes[i] == b'+' || bytes[i] == b'-') {
+            i += 1;
+        }
+
+        i < bytes.len() && bytes[i].is_ascii_digit()
     }
 
     fn consume_whitespace(&mut self) {
@@ -313,28 +557,59 @@ impl<'a> Lexer<'a> {
     }
 }
 
-fn is_ident_start(c: char) -> bool {
-    match c {
-        'a'...'z' |
-        'A'...'Z' |
-        '\u{80}'...'\u{FF}' |
-        '_' => true,
-        _ => false,
+impl<'a> Iterator for Lexer<'a> {
+    type Item = Result<LexedToken<'a>, SyntaxError>;
+
+    fn next(&mut self) -> Option<Result<LexedToken<'a>, SyntaxError>> {
+        if self.done {
+            return None;
+        }
+
+        let result = self.next_token();
+
+        // Stop after `Eof`, and also after any error: an unexpected character
+        // is not consumed, so lexing on would repeat the same error.
+        match result {
+            Ok(LexedToken { token: Token::Eof, .. }) | Err(_) => self.done = true,
+            _ => {}
+        }
+
+        Some(result)
     }
 }
 
-fn is_ident_cont(c: char) -> bool {
-    match c {
-        'a'...'z' |
-        'A'...'Z' |
-        '\u{80}'...'\u{FF}' |
-        '0'...'9' |
-        '_' |
-        '$' => true,
-        _ => false,
+/// Lexes the whole input, collecting every token with its span into a vector.
+///
+/// The `Eof` token is included as the final element. Returns the first error
+/// encountered, if any.
+pub fn lex(input: &str) -> Result<Vec<LexedToken>, SyntaxError> {
+    Lexer::new(input).collect()
+}
+
+/// Lower cases an unquoted identifier, borrowing the input unchanged when it is
+/// already lower case and only allocating for mixed-case identifiers.
+fn fold_ident(s: &str) -> Cow<str> {
+    if s.chars().any(char::is_uppercase) {
+        Cow::Owned(s.to_lowercase())
+    } else {
+        Cow::Borrowed(s)
     }
 }
 
+fn is_number_start(c: char, next: Option<char>) -> bool {
+    c.is_ascii_digit() || (c == '.' && next.map_or(false, |n| n.is_ascii_digit()))
+}
+
+fn is_ident_start(c: char) -> bool {
+    // `_` is a SQL-specific addition to the Unicode `XID_Start` set.
+    c == '_' || UnicodeXID::is_xid_start(c)
+}
+
+fn is_ident_cont(c: char) -> bool {
+    // `_` and `$` are SQL-specific additions to the Unicode `XID_Continue` set.
+    c == '_' || c == '$' || UnicodeXID::is_xid_continue(c)
+}
+
 fn is_single_byte_op_char(c: char) -> bool {
     match c {
         '+' | '-' | '*' | '/' | '%' | '=' | '<' | '>' => true,
@@ -363,11 +638,17 @@ mod tests {
     use parser::token::{Token, Keyword, Op};
     use parser::errors::SyntaxError;
 
-    fn expected_token(token: Token,
-                      start: BytePos,
-                      end: BytePos)
-                      -> Result<LexedToken, SyntaxError> {
-        Ok(LexedToken::new(token, start, end))
+    // All the inputs exercised below are single line, so byte position `p` maps
+    // to line 1, column `p + 1`.
+    fn expected_token<'a>(token: Token<'a>,
+                          start: BytePos,
+                          end: BytePos)
+                          -> Result<LexedToken<'a>, SyntaxError> {
+        Ok(LexedToken::new(token,
+                           start,
+                           end,
+                           LineCol::new(1, start + 1),
+                           LineCol::new(1, end + 1)))
     }
 
     #[test]
@@ -388,7 +669,7 @@ mod tests {
     fn returns_comment_token_for_dash_dash_comment_only() {
         let mut lexer = Lexer::new("-- comment");
 
-        assert_eq!(expected_token(Token::Comment(" comment".to_string()), 0, 10),
+        assert_eq!(expected_token(Token::Comment(" comment"), 0, 10),
                    lexer.next_token());
 
         assert_eq!(expected_token(Token::Eof, 10, 10), lexer.next_token());
@@ -417,28 +698,177 @@ mod tests {
     fn returns_downcased_unquoted_identifiers() {
         let mut lexer = Lexer::new("_foo BaR IDENT2 ídèñt$3_");
 
-        assert_eq!(expected_token(Token::Ident("_foo".to_string()), 0, 3),
+        assert_eq!(expected_token(Token::Ident("_foo".into()), 0, 3),
                    lexer.next_token());
 
         assert_eq!(expected_token(Token::Whitespace, 4, 4), lexer.next_token());
 
-        assert_eq!(expected_token(Token::Ident("bar".to_string()), 5, 7),
+        assert_eq!(expected_token(Token::Ident("bar".into()), 5, 7),
                    lexer.next_token());
 
         assert_eq!(expected_token(Token::Whitespace, 8, 8), lexer.next_token());
 
-        assert_eq!(expected_token(Token::Ident("ident2".to_string()), 9, 14),
+        assert_eq!(expected_token(Token::Ident("ident2".into()), 9, 14),
                    lexer.next_token());
 
         assert_eq!(expected_token(Token::Whitespace, 15, 15),
                    lexer.next_token());
 
-        assert_eq!(expected_token(Token::Ident("ídèñt$3_".to_string()), 16, 26),
+        assert_eq!(expected_token(Token::Ident("ídèñt$3_".into()), 16, 26),
                    lexer.next_token());
 
         assert_eq!(expected_token(Token::Eof, 26, 26), lexer.next_token());
     }
 
+    #[test]
+    fn accepts_unicode_identifiers() {
+        // `café` and a CJK identifier are valid XID_Start/XID_Continue sequences.
+        let mut lexer = Lexer::new("café 名前");
+
+        assert_eq!(expected_token(Token::Ident("café".into()), 0, 3),
+                   lexer.next_token());
+
+        assert_eq!(expected_token(Token::Whitespace, 5, 5), lexer.next_token());
+
+        assert_eq!(expected_token(Token::Ident("名前".into()), 6, 9),
+                   lexer.next_token());
+    }
+
+    #[test]
+    fn recognizes_integer_and_float_literals() {
+        let mut lexer = Lexer::new("42 3.14 1.5e-10 .5");
+
+        assert_eq!(expected_token(Token::Int(42), 0, 1), lexer.next_token());
+
+        assert_eq!(expected_token(Token::Whitespace, 2, 2), lexer.next_token());
+
+        assert_eq!(expected_token(Token::Float(3.14), 3, 6), lexer.next_token());
+
+        assert_eq!(expected_token(Token::Whitespace, 7, 7), lexer.next_token());
+
+        assert_eq!(expected_token(Token::Float(1.5e-10), 8, 14),
+                   lexer.next_token());
+
+        assert_eq!(expected_token(Token::Whitespace, 15, 15),
+                   lexer.next_token());
+
+        assert_eq!(expected_token(Token::Float(0.5), 16, 17), lexer.next_token());
+
+        assert_eq!(expected_token(Token::Eof, 17, 17), lexer.next_token());
+    }
+
+    #[test]
+    fn does_not_consume_a_trailing_dot_into_a_number() {
+        // The `.` is left for the caller (e.g. `t1.col` member access) rather
+        // than being folded into the numeric literal.
+        let mut lexer = Lexer::new("1.");
+
+        assert_eq!(expected_token(Token::Int(1), 0, 0), lexer.next_token());
+    }
+
+    #[test]
+    fn reports_invalid_number_for_overflow() {
+        let mut lexer = Lexer::new("99999999999999999999");
+
+        match lexer.next_token() {
+            Err(SyntaxError::InvalidNumber { span }) => assert_eq!(span, Span::new(0, 19)),
+            other => panic!("expected InvalidNumber, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn unescapes_doubled_quotes_in_string_literals() {
+        let mut lexer = Lexer::new("'it''s'");
+
+        assert_eq!(expected_token(Token::String("it's".to_string()), 0, 6),
+                   lexer.next_token());
+
+        assert_eq!(expected_token(Token::Eof, 6, 6), lexer.next_token());
+    }
+
+    #[test]
+    fn preserves_case_in_quoted_identifiers() {
+        let mut lexer = Lexer::new("\"Select\"");
+
+        assert_eq!(expected_token(Token::Ident("Select".into()), 0, 7),
+                   lexer.next_token());
+
+        assert_eq!(expected_token(Token::Eof, 7, 7), lexer.next_token());
+    }
+
+    #[test]
+    fn reports_unterminated_string_literal() {
+        let mut lexer = Lexer::new("'abc");
+
+        match lexer.next_token() {
+            Err(SyntaxError::UnterminatedLiteral { span }) => {
+                assert_eq!(span, Span::new(0, 3))
+            }
+            other => panic!("expected UnterminatedLiteral, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn tracks_line_and_column_across_newlines() {
+        let mut lexer = Lexer::new("a\nbc");
+
+        let a = lexer.next_token().unwrap();
+        assert_eq!(Span::new(0, 0), a.span);
+        assert_eq!(LineColSpan::new(LineCol::new(1, 1), LineCol::new(1, 1)), a.lines);
+
+        lexer.next_token().unwrap(); // newline whitespace
+
+        let bc = lexer.next_token().unwrap();
+        assert_eq!(Span::new(2, 3), bc.span);
+        assert_eq!(LineColSpan::new(LineCol::new(2, 1), LineCol::new(2, 2)), bc.lines);
+    }
+
+    #[test]
+    fn lex_collects_every_token_including_eof() {
+        let tokens = lex("1 + 2").unwrap();
+
+        let kinds: Vec<Token> = tokens.into_iter().map(|t| t.token).collect();
+        assert_eq!(vec![Token::Int(1),
+                        Token::Whitespace,
+                        Token::Op(Op::Plus),
+                        Token::Whitespace,
+                        Token::Int(2),
+                        Token::Eof],
+                   kinds);
+    }
+
+    #[test]
+    fn lex_surfaces_the_first_error() {
+        assert_eq!(Err(SyntaxError::UnexpectedChar { ch: '@', pos: 0 }), lex("@"));
+    }
+
+    #[test]
+    fn iterator_stops_after_eof() {
+        let mut lexer = Lexer::new("");
+
+        assert_eq!(Some(expected_token(Token::Eof, 0, 0)), lexer.next());
+        assert_eq!(None, lexer.next());
+    }
+
+    #[test]
+    fn iterator_combinators_can_drop_whitespace() {
+        let idents: Vec<Token> = Lexer::new("a b")
+            .filter_map(Result::ok)
+            .map(|t| t.token)
+            .filter(|t| *t != Token::Whitespace && *t != Token::Eof)
+            .collect();
+
+        assert_eq!(vec![Token::Ident("a".into()), Token::Ident("b".into())], idents);
+    }
+
+    #[test]
+    fn reports_unexpected_character() {
+        let mut lexer = Lexer::new("@");
+
+        assert_eq!(Err(SyntaxError::UnexpectedChar { ch: '@', pos: 0 }),
+                   lexer.next_token());
+    }
+
     #[test]
     fn recognizes_operators_surrounded_by_whitespace() {
         let mut lexer = Lexer::new("+ - * / % = != <> <= >= < >");
@@ -1,13 +1,19 @@
+use std::borrow::Cow;
 use std::str::FromStr;
 
-#[derive(Debug, PartialEq, Eq)]
-pub enum Token {
+// `Token` carries an `f64` in `Float`, so it cannot derive `Eq`.
+#[derive(Debug, PartialEq)]
+pub enum Token<'a> {
     Keyword(Keyword),
-    Ident(String),
+    Ident(Cow<'a, str>),
     Op(Op),
 
+    Int(i64),
+    Float(f64),
+    String(String),
+
     Whitespace,
-    Comment(String),
+    Comment(&'a str),
     Eof,
 }
 